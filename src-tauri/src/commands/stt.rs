@@ -1,19 +1,545 @@
 use anyhow::{Context, Result};
+use async_trait::async_trait;
+use candle_core::Device;
+use candle_transformers::models::whisper::{self as whisper_model, model::Whisper};
 use chrono::Utc;
+use realfft::RealFftPlanner;
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
-use std::sync::Mutex;
-use tauri::State;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokenizers::Tokenizer;
+use tokio::sync::oneshot;
+use tokio::time::Duration;
 use crate::commands::agents::AgentDb;
 use base64::{Engine as _, engine::general_purpose};
 
-/// Global state for active recording process
-pub struct RecordingProcess(pub Mutex<Option<(Child, PathBuf)>>);
+/// Global state for active recording process. Holds whichever backend is
+/// currently capturing -- the `arecord` subprocess or an in-process cpal
+/// stream -- behind the `AudioRecorder` trait.
+pub struct RecordingProcess(pub Mutex<Option<(Box<dyn AudioRecorder>, PathBuf)>>);
+
+/// Common interface for recording backends, so `RecordingProcess` doesn't
+/// need to know whether it's driving the `arecord` subprocess or an
+/// in-process cpal stream. Implementors must finish writing the WAV file to
+/// disk by the time `stop` returns.
+pub trait AudioRecorder: Send {
+    fn stop(&mut self) -> Result<()>;
+}
+
+/// Backend wrapping the `arecord` subprocess, kept as the fallback for the
+/// WebKitGTK-broken Linux setups where an in-process audio stream can
+/// misbehave (see `start_subprocess_recording`).
+struct ArecordBackend(Child);
+
+impl AudioRecorder for ArecordBackend {
+    fn stop(&mut self) -> Result<()> {
+        stop_child_process(&mut self.0)?;
+        Ok(())
+    }
+}
+
+/// Backend capturing audio in-process via cpal, so recording works without
+/// `arecord`/ALSA tools installed (macOS, Windows, minimal Linux images).
+/// The capture stream itself lives on a dedicated OS thread, since
+/// `cpal::Stream` is not `Send`; this struct only holds the handles needed
+/// to stop it.
+struct CpalBackend {
+    stop_tx: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl AudioRecorder for CpalBackend {
+    fn stop(&mut self) -> Result<()> {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        Ok(())
+    }
+}
+
+/// Start an in-process cpal capture on `device_name` (or the host's default
+/// input device), writing 16kHz mono S16_LE PCM to `file_path` as it
+/// arrives -- same format and growth-while-recording behavior as the
+/// `arecord` backend, so `spawn_vad_monitor` doesn't need to know which
+/// backend produced the file.
+fn start_cpal_recording(device_name: Option<String>, file_path: PathBuf) -> Result<CpalBackend> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    // Reserve the 44-byte WAV header up front; patched with real sizes on stop.
+    write_wav_mono_i16(&file_path, &[])?;
+
+    let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel::<std::result::Result<(), String>>();
+
+    let thread = std::thread::spawn(move || {
+        use cpal::traits::StreamTrait;
+
+        let host = cpal::default_host();
+        let device = device_name
+            .as_ref()
+            .and_then(|name| {
+                host.input_devices().ok().and_then(|mut devices| {
+                    devices.find(|d| d.name().map(|n| &n == name).unwrap_or(false))
+                })
+            })
+            .or_else(|| host.default_input_device());
+
+        let device = match device {
+            Some(d) => d,
+            None => {
+                let _ = ready_tx.send(Err("No input device available".to_string()));
+                return;
+            }
+        };
+
+        let config = match device.default_input_config() {
+            Ok(c) => c,
+            Err(e) => {
+                let _ = ready_tx.send(Err(format!("Failed to get input config: {}", e)));
+                return;
+            }
+        };
+
+        let channels = config.channels() as usize;
+        let ratio = VAD_SAMPLE_RATE as f64 / config.sample_rate().0 as f64;
+        let sample_format = config.sample_format();
+        let stream_config: cpal::StreamConfig = config.into();
+
+        let file = match std::fs::OpenOptions::new().write(true).open(&file_path) {
+            Ok(f) => Arc::new(Mutex::new(f)),
+            Err(e) => {
+                let _ = ready_tx.send(Err(format!("Failed to open recording file: {}", e)));
+                return;
+            }
+        };
+
+        let err_fn = |e| log::error!("cpal input stream error: {}", e);
+        let write_mono = {
+            let file = file.clone();
+            let mut ratio_acc = 0.0f64;
+            move |mono: &[i16]| {
+                use std::io::{Seek, SeekFrom, Write};
+                let mut kept = Vec::with_capacity(mono.len());
+                for &sample in mono {
+                    // Naive decimation down to 16kHz -- recordings here are
+                    // short voice clips, so a heavier resampler isn't worth it.
+                    ratio_acc += ratio;
+                    if ratio_acc >= 1.0 {
+                        ratio_acc -= 1.0;
+                        kept.push(sample);
+                    }
+                }
+                if kept.is_empty() {
+                    return;
+                }
+                if let Ok(mut f) = file.lock() {
+                    if f.seek(SeekFrom::End(0)).is_ok() {
+                        for sample in kept {
+                            let _ = f.write_all(&sample.to_le_bytes());
+                        }
+                    }
+                }
+            }
+        };
+
+        let stream = match sample_format {
+            cpal::SampleFormat::I16 => {
+                let mut write_mono = write_mono;
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[i16], _: &cpal::InputCallbackInfo| {
+                        let mono: Vec<i16> = data.chunks(channels).map(average_channels_i16).collect();
+                        write_mono(&mono);
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            cpal::SampleFormat::U16 => {
+                let mut write_mono = write_mono;
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[u16], _: &cpal::InputCallbackInfo| {
+                        let mono: Vec<i16> = data.chunks(channels).map(average_channels_u16).collect();
+                        write_mono(&mono);
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            cpal::SampleFormat::I32 => {
+                let mut write_mono = write_mono;
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[i32], _: &cpal::InputCallbackInfo| {
+                        let mono: Vec<i16> = data.chunks(channels).map(average_channels_i32).collect();
+                        write_mono(&mono);
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            cpal::SampleFormat::F32 => {
+                let mut write_mono = write_mono;
+                device.build_input_stream(
+                    &stream_config,
+                    move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                        let mono: Vec<i16> = data
+                            .chunks(channels)
+                            .map(|frame| (average_channels_f32(frame) * i16::MAX as f32) as i16)
+                            .collect();
+                        write_mono(&mono);
+                    },
+                    err_fn,
+                    None,
+                )
+            }
+            other => {
+                let _ = ready_tx.send(Err(format!("Unsupported input sample format: {:?}", other)));
+                return;
+            }
+        };
+
+        let stream = match stream {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = ready_tx.send(Err(format!("Failed to build input stream: {}", e)));
+                return;
+            }
+        };
+
+        if let Err(e) = stream.play() {
+            let _ = ready_tx.send(Err(format!("Failed to start input stream: {}", e)));
+            return;
+        }
+
+        let _ = ready_tx.send(Ok(()));
+
+        // Block until told to stop; dropping `stream` here ends capture.
+        let _ = stop_rx.recv();
+        drop(stream);
+
+        if let Err(e) = finalize_wav_header(&file_path) {
+            log::error!("Failed to finalize WAV header: {}", e);
+        }
+    });
+
+    ready_rx
+        .recv()
+        .context("cpal capture thread exited before starting")?
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    Ok(CpalBackend {
+        stop_tx: Some(stop_tx),
+        thread: Some(thread),
+    })
+}
+
+fn average_channels_i16(frame: &[i16]) -> i16 {
+    let sum: i64 = frame.iter().map(|&s| s as i64).sum();
+    (sum / frame.len().max(1) as i64) as i16
+}
+
+fn average_channels_f32(frame: &[f32]) -> f32 {
+    frame.iter().sum::<f32>() / frame.len().max(1) as f32
+}
+
+/// Average a frame of unsigned 16-bit samples, re-centering to the signed
+/// range `write_mono` expects (cpal's `U16` format is unsigned with a
+/// `32768` midpoint rather than signed around zero).
+fn average_channels_u16(frame: &[u16]) -> i16 {
+    let sum: i64 = frame.iter().map(|&s| s as i64 - i64::from(u16::MAX / 2 + 1)).sum();
+    (sum / frame.len().max(1) as i64) as i16
+}
+
+/// Average a frame of signed 32-bit samples, scaling down to 16-bit by
+/// dropping the low 16 bits of each sample.
+fn average_channels_i32(frame: &[i32]) -> i16 {
+    let sum: i64 = frame.iter().map(|&s| (s >> 16) as i64).sum();
+    (sum / frame.len().max(1) as i64) as i16
+}
+
+/// Handle to the background task watching an active recording for trailing
+/// silence, so it can be cancelled if the recording is stopped manually.
+#[derive(Default)]
+pub struct VadMonitor(pub Mutex<Option<oneshot::Sender<()>>>);
+
+const VAD_FRAME_MS: usize = 20;
+const VAD_SAMPLE_RATE: usize = 16_000;
+const VAD_FRAME_SAMPLES: usize = VAD_SAMPLE_RATE * VAD_FRAME_MS / 1000;
+const VAD_SPEECH_BAND_HZ: (f32, f32) = (300.0, 3400.0);
+const VAD_NOISE_FLOOR_FRAMES: usize = 50; // ~1s of trailing frames
+const VAD_POLL_INTERVAL_MS: u64 = 200;
+const WAV_HEADER_BYTES: u64 = 44;
+
+/// Rolling voice-activity detector over 20ms frames of 16kHz mono PCM.
+///
+/// Computes a Hann-windowed FFT per frame and sums squared magnitude across
+/// the speech band (300-3400Hz) to get a frame energy. Speech is declared
+/// when that energy exceeds an adaptive noise floor -- the running minimum
+/// of recent frame energies -- times a sensitivity multiplier.
+struct VoiceActivityDetector {
+    fft: Arc<dyn realfft::RealToComplex<f32>>,
+    window: Vec<f32>,
+    recent_energies: VecDeque<f32>,
+    sensitivity: f32,
+    band_range: std::ops::Range<usize>,
+}
+
+impl VoiceActivityDetector {
+    fn new(sensitivity: f32) -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(VAD_FRAME_SAMPLES);
+
+        let window: Vec<f32> = (0..VAD_FRAME_SAMPLES)
+            .map(|i| {
+                0.5 * (1.0
+                    - ((2.0 * std::f32::consts::PI * i as f32) / (VAD_FRAME_SAMPLES as f32 - 1.0))
+                        .cos())
+            })
+            .collect();
+
+        let bin_hz = VAD_SAMPLE_RATE as f32 / VAD_FRAME_SAMPLES as f32;
+        let band_range =
+            ((VAD_SPEECH_BAND_HZ.0 / bin_hz) as usize)..((VAD_SPEECH_BAND_HZ.1 / bin_hz) as usize);
+
+        Self {
+            fft,
+            window,
+            recent_energies: VecDeque::with_capacity(VAD_NOISE_FLOOR_FRAMES),
+            sensitivity,
+            band_range,
+        }
+    }
+
+    /// Returns `true` if `frame` (exactly `VAD_FRAME_SAMPLES` samples) contains speech.
+    fn is_speech(&mut self, frame: &[f32]) -> bool {
+        let mut windowed: Vec<f32> = frame.iter().zip(&self.window).map(|(s, w)| s * w).collect();
+
+        let mut spectrum = self.fft.make_output_vec();
+        if self.fft.process(&mut windowed, &mut spectrum).is_err() {
+            return false;
+        }
+
+        let band_energy: f32 = spectrum[self.band_range.clone()]
+            .iter()
+            .map(|c| c.norm_sqr())
+            .sum();
+
+        let noise_floor = self.recent_energies.iter().cloned().fold(f32::INFINITY, f32::min);
+        let noise_floor = if noise_floor.is_finite() { noise_floor } else { band_energy };
+
+        if self.recent_energies.len() == VAD_NOISE_FLOOR_FRAMES {
+            self.recent_energies.pop_front();
+        }
+        self.recent_energies.push_back(band_energy);
+
+        band_energy > noise_floor * self.sensitivity
+    }
+}
+
+/// Trim leading and trailing non-speech frames from a buffer of 16kHz mono
+/// PCM samples, using the same speech/non-speech boundaries as the auto-stop
+/// VAD so uploads don't waste transcription seconds on silence.
+fn trim_silence(samples: &[f32], sensitivity: f32) -> Vec<f32> {
+    let mut vad = VoiceActivityDetector::new(sensitivity);
+    let speech_flags: Vec<bool> = samples
+        .chunks(VAD_FRAME_SAMPLES)
+        .filter(|frame| frame.len() == VAD_FRAME_SAMPLES)
+        .map(|frame| vad.is_speech(frame))
+        .collect();
+
+    match (speech_flags.iter().position(|&s| s), speech_flags.iter().rposition(|&s| s)) {
+        (Some(first), Some(last)) => {
+            let start = first * VAD_FRAME_SAMPLES;
+            let end = ((last + 1) * VAD_FRAME_SAMPLES).min(samples.len());
+            samples[start..end].to_vec()
+        }
+        _ => samples.to_vec(),
+    }
+}
+
+/// Write 16kHz mono PCM16 samples as a canonical WAV file (44-byte header).
+fn write_wav_mono_i16(path: &std::path::Path, samples: &[f32]) -> Result<()> {
+    let mut bytes = Vec::with_capacity(WAV_HEADER_BYTES as usize + samples.len() * 2);
+    let data_len = (samples.len() * 2) as u32;
+    let byte_rate = VAD_SAMPLE_RATE as u32 * 2;
+
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&(VAD_SAMPLE_RATE as u32).to_le_bytes());
+    bytes.extend_from_slice(&byte_rate.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+
+    for sample in samples {
+        let clamped = sample.clamp(-1.0, 1.0);
+        bytes.extend_from_slice(&((clamped * i16::MAX as f32) as i16).to_le_bytes());
+    }
+
+    std::fs::write(path, bytes).context("Failed to write trimmed WAV file")
+}
+
+/// Trim leading/trailing silence from the WAV at `file_path` in place.
+fn trim_silence_in_place(file_path: &std::path::Path, sensitivity: f32) -> Result<()> {
+    let samples = read_wav_mono_f32(file_path)?;
+    let trimmed = trim_silence(&samples, sensitivity);
+    write_wav_mono_i16(file_path, &trimmed)
+}
+
+/// Patch the RIFF/data chunk sizes of a WAV file whose header was written
+/// with placeholder sizes (a zero-length data chunk) before the final length was known -- used
+/// to finalize files streamed to disk incrementally by the cpal backend.
+fn finalize_wav_header(path: &std::path::Path) -> Result<()> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let metadata = std::fs::metadata(path).context("Failed to stat recording file")?;
+    let data_len = metadata.len().saturating_sub(WAV_HEADER_BYTES) as u32;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .context("Failed to open recording file to finalize header")?;
+
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&(36 + data_len).to_le_bytes())?;
+    file.seek(SeekFrom::Start(40))?;
+    file.write_all(&data_len.to_le_bytes())?;
+
+    Ok(())
+}
+
+/// Watch `file_path` for trailing silence while it's being recorded into,
+/// and auto-finalize the recording once `silence_ms` of trailing non-speech
+/// has elapsed. Emits `recording-auto-stopped` with the final file path.
+fn spawn_vad_monitor(
+    app: AppHandle,
+    file_path: PathBuf,
+    silence_ms: u64,
+    sensitivity: f32,
+) -> oneshot::Sender<()> {
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+
+    tauri::async_runtime::spawn(async move {
+        let mut vad = VoiceActivityDetector::new(sensitivity);
+        let mut offset: u64 = WAV_HEADER_BYTES;
+        let mut pending = Vec::<f32>::new();
+        let mut consecutive_silence_frames: u64 = 0;
+        let mut has_spoken = false;
+        let frames_for_silence = silence_ms / VAD_FRAME_MS as u64;
+
+        loop {
+            if stop_rx.try_recv().is_ok() {
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_millis(VAD_POLL_INTERVAL_MS)).await;
+
+            let Ok(metadata) = std::fs::metadata(&file_path) else { continue };
+            if metadata.len() <= offset {
+                continue;
+            }
+
+            let Ok(mut file) = std::fs::File::open(&file_path) else { continue };
+            use std::io::{Read, Seek, SeekFrom};
+            if file.seek(SeekFrom::Start(offset)).is_err() {
+                continue;
+            }
+            let mut new_bytes = Vec::new();
+            if file.read_to_end(&mut new_bytes).is_err() {
+                continue;
+            }
+            offset += new_bytes.len() as u64;
+
+            pending.extend(
+                new_bytes
+                    .chunks_exact(2)
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32),
+            );
+
+            while pending.len() >= VAD_FRAME_SAMPLES {
+                let frame: Vec<f32> = pending.drain(..VAD_FRAME_SAMPLES).collect();
+                if vad.is_speech(&frame) {
+                    has_spoken = true;
+                    consecutive_silence_frames = 0;
+                } else {
+                    consecutive_silence_frames += 1;
+                }
+            }
+
+            if has_spoken && consecutive_silence_frames >= frames_for_silence {
+                log::info!("VAD detected {}ms of trailing silence, auto-stopping recording", silence_ms);
+
+                let recording_state = app.state::<RecordingProcess>();
+                let mut state = match recording_state.0.lock() {
+                    Ok(state) => state,
+                    Err(_) => return,
+                };
+
+                if let Some((mut backend, path)) = state.take() {
+                    drop(state);
+                    let _ = backend.stop();
+                    let _ = trim_silence_in_place(&path, sensitivity);
+                    let _ = app.emit("recording-auto-stopped", path.to_string_lossy().to_string());
+                }
+
+                return;
+            }
+        }
+    });
+
+    stop_tx
+}
+
+/// Send SIGTERM (unix) to a recording subprocess and wait for it to exit.
+fn stop_child_process(child: &mut Child) -> std::io::Result<std::process::ExitStatus> {
+    #[cfg(unix)]
+    {
+        let pid = child.id();
+        unsafe {
+            libc::kill(pid as i32, libc::SIGTERM);
+        }
+    }
+    child.wait()
+}
+
+/// Lazily-loaded local Whisper model, cached across `transcribe_audio` calls
+/// so the (potentially large) model weights are only read from disk once.
+///
+/// Only the model weights live in here. Per-call scratch (mel spectrograms,
+/// intermediate tensors) must stay local to `transcribe_with_local` and be
+/// dropped when it returns -- holding them in this cached state is what
+/// causes the well-documented Candle memory growth on repeated macOS
+/// inference calls.
+#[derive(Default)]
+pub struct LocalWhisperModel(pub Mutex<Option<LoadedWhisperModel>>);
+
+pub struct LoadedWhisperModel {
+    model_path: PathBuf,
+    model: Whisper,
+    tokenizer: Tokenizer,
+    device: Device,
+    /// Mel filterbank for this model's `num_mel_bins`, loaded once alongside
+    /// the weights since it only depends on the model, not the call.
+    mel_filters: Vec<f32>,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptionResponse {
+    #[serde(default)]
     pub model: String,
     pub text: String,
     pub language: Option<String>,
@@ -33,6 +559,96 @@ pub struct TranscriptionUsage {
     pub completion_tokens: u64,
 }
 
+/// A segment of transcribed audio with its timing, as surfaced by providers
+/// that support segment-level timestamps (both Mistral and OpenAI do,
+/// though under slightly different field names in the raw response).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// Result of a batch transcription call, returned to the frontend so
+/// callers can build word/segment-timed captions instead of just a flat string.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionResult {
+    pub text: String,
+    pub segments: Vec<TranscriptionSegment>,
+}
+
+/// Best-effort conversion of a provider's raw segment JSON into our
+/// normalized shape. Providers disagree on field names for the same data
+/// (`start`/`start_time`, `text`/`content`), so parse permissively and drop
+/// anything that doesn't match rather than failing the whole transcription.
+fn parse_segments(raw: &[serde_json::Value]) -> Vec<TranscriptionSegment> {
+    raw.iter()
+        .filter_map(|v| {
+            let start = v.get("start").or_else(|| v.get("start_time"))?.as_f64()?;
+            let end = v.get("end").or_else(|| v.get("end_time"))?.as_f64()?;
+            let text = v
+                .get("text")
+                .or_else(|| v.get("content"))?
+                .as_str()?
+                .to_string();
+            Some(TranscriptionSegment { start, end, text })
+        })
+        .collect()
+}
+
+/// A transcription backend reachable over HTTP multipart upload (Mistral,
+/// OpenAI). `transcribe_audio` dispatches to one of these based on its
+/// `provider` argument; `local` mode bypasses this trait since it
+/// doesn't speak the same request/response shape.
+#[async_trait]
+trait TranscriptionProvider: Send + Sync {
+    async fn transcribe(
+        &self,
+        audio_path: PathBuf,
+        api_key: String,
+        language: Option<String>,
+    ) -> Result<TranscriptionResult>;
+}
+
+struct MistralProvider;
+
+#[async_trait]
+impl TranscriptionProvider for MistralProvider {
+    async fn transcribe(
+        &self,
+        audio_path: PathBuf,
+        api_key: String,
+        language: Option<String>,
+    ) -> Result<TranscriptionResult> {
+        transcribe_with_mistral(audio_path, api_key, language).await
+    }
+}
+
+struct OpenAiProvider;
+
+#[async_trait]
+impl TranscriptionProvider for OpenAiProvider {
+    async fn transcribe(
+        &self,
+        audio_path: PathBuf,
+        api_key: String,
+        language: Option<String>,
+    ) -> Result<TranscriptionResult> {
+        transcribe_with_openai(audio_path, api_key, language).await
+    }
+}
+
+/// Resolve the stored provider name to a `TranscriptionProvider`, defaulting
+/// to Mistral for unrecognized values (matches the prior hardcoded behavior).
+fn resolve_provider(name: &str) -> Box<dyn TranscriptionProvider> {
+    match name {
+        "openai" => Box::new(OpenAiProvider),
+        _ => Box::new(MistralProvider),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SttSettings {
@@ -40,64 +656,128 @@ pub struct SttSettings {
     pub api_key: Option<String>,
     pub model: String,
     pub language: Option<String>,
+    /// Path to a local Whisper model (safetensors weights) used when
+    /// `provider` is `"local"`.
+    pub local_model_path: Option<String>,
+    /// Auto-stop recording (and trim leading/trailing silence before upload)
+    /// once trailing silence exceeds `silence_ms`.
+    #[serde(default)]
+    pub vad_enabled: bool,
+    /// How long a trailing run of non-speech audio must last before the VAD
+    /// auto-finalizes the recording.
+    #[serde(default = "default_silence_ms")]
+    pub silence_ms: u64,
+    /// Multiplier applied to the adaptive noise floor: a frame is "speech"
+    /// when its band energy exceeds `floor * sensitivity`. Higher values
+    /// require louder speech to trigger.
+    #[serde(default = "default_vad_sensitivity")]
+    pub sensitivity: f32,
+    /// Name of the input device to record from (cpal backend), or `None` for
+    /// the host's default input device.
+    pub input_device: Option<String>,
+}
+
+fn default_silence_ms() -> u64 {
+    1_500
 }
 
-/// Start recording audio using subprocess (Linux fallback for WebKitGTK issues)
+fn default_vad_sensitivity() -> f32 {
+    2.0
+}
+
+/// Start recording audio. Prefers an in-process cpal capture (works on
+/// macOS/Windows/minimal Linux with no external tools), falling back to
+/// shelling out to `arecord` only if cpal capture can't be started; there is
+/// no option to select `arecord` directly, so on Linux setups where
+/// WebKitGTK's audio handling conflicts with an in-process stream that still
+/// starts, cpal is used. Kept under its
+/// original name alongside `stop_subprocess_recording`/
+/// `cancel_subprocess_recording` so the trio stays consistent even though
+/// cpal, not a subprocess, is now the common-case backend.
 #[tauri::command]
 pub async fn start_subprocess_recording(
+    app: AppHandle,
     recording_state: State<'_, RecordingProcess>,
+    vad_monitor: State<'_, VadMonitor>,
+    input_device: Option<String>,
+    vad_enabled: bool,
+    silence_ms: u64,
+    sensitivity: f32,
 ) -> Result<String, String> {
     let temp_dir = std::env::temp_dir();
     let file_path = temp_dir.join(format!("recording_{}.wav", Utc::now().timestamp()));
 
-    log::info!("Starting subprocess recording to: {:?}", file_path);
-
-    // Use arecord to capture audio
-    // -f S16_LE: 16-bit signed little-endian PCM
-    // -r 16000: 16kHz sample rate (good for speech)
-    // -c 1: mono
-    let child = Command::new("arecord")
-        .args([
-            "-f", "S16_LE",
-            "-r", "16000",
-            "-c", "1",
-            file_path.to_str().unwrap(),
-        ])
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|e| format!("Failed to start arecord: {}. Is arecord installed?", e))?;
+    log::info!("Starting recording to: {:?}", file_path);
+
+    let backend: Box<dyn AudioRecorder> = match start_cpal_recording(input_device, file_path.clone()) {
+        Ok(cpal_backend) => {
+            log::info!("Recording via cpal capture backend");
+            Box::new(cpal_backend)
+        }
+        Err(e) => {
+            log::warn!("cpal capture unavailable ({}), falling back to arecord", e);
+
+            // -f S16_LE: 16-bit signed little-endian PCM
+            // -r 16000: 16kHz sample rate (good for speech)
+            // -c 1: mono
+            let child = Command::new("arecord")
+                .args(["-f", "S16_LE", "-r", "16000", "-c", "1", file_path.to_str().unwrap()])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .map_err(|e| format!("Failed to start arecord: {}. Is arecord installed?", e))?;
+
+            Box::new(ArecordBackend(child))
+        }
+    };
 
     let mut state = recording_state.0.lock().map_err(|e| e.to_string())?;
-    *state = Some((child, file_path.clone()));
+    *state = Some((backend, file_path.clone()));
+    drop(state);
+
+    let mut monitor = vad_monitor.0.lock().map_err(|e| e.to_string())?;
+    *monitor = if vad_enabled {
+        Some(spawn_vad_monitor(app, file_path.clone(), silence_ms, sensitivity))
+    } else {
+        None
+    };
 
     log::info!("Recording started successfully");
     Ok(file_path.to_string_lossy().to_string())
 }
 
+/// List available audio input devices by name, for the `stt_input_device` setting.
+#[tauri::command]
+pub async fn list_input_devices() -> Result<Vec<String>, String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let host = cpal::default_host();
+    let devices = host
+        .input_devices()
+        .map_err(|e| format!("Failed to enumerate input devices: {}", e))?;
+
+    Ok(devices.filter_map(|d| d.name().ok()).collect())
+}
+
 /// Stop the active recording and return the file path
 #[tauri::command]
 pub async fn stop_subprocess_recording(
     recording_state: State<'_, RecordingProcess>,
+    vad_monitor: State<'_, VadMonitor>,
+    trim_silence: bool,
+    sensitivity: f32,
 ) -> Result<String, String> {
+    if let Some(stop_tx) = vad_monitor.0.lock().map_err(|e| e.to_string())?.take() {
+        let _ = stop_tx.send(());
+    }
+
     let mut state = recording_state.0.lock().map_err(|e| e.to_string())?;
 
-    if let Some((mut child, file_path)) = state.take() {
+    if let Some((mut backend, file_path)) = state.take() {
         log::info!("Stopping recording process...");
 
-        // Send SIGTERM to arecord to stop recording gracefully
-        #[cfg(unix)]
-        {
-            use std::os::unix::process::CommandExt;
-            let pid = child.id();
-            unsafe {
-                libc::kill(pid as i32, libc::SIGTERM);
-            }
-        }
-
-        // Wait for process to finish (with timeout)
-        let _ = child.wait();
+        backend.stop().map_err(|e| e.to_string())?;
 
         log::info!("Recording stopped, file saved to: {:?}", file_path);
 
@@ -107,6 +787,11 @@ pub async fn stop_subprocess_recording(
                 .map_err(|e| format!("Failed to read file metadata: {}", e))?;
 
             if metadata.len() > 0 {
+                if trim_silence {
+                    if let Err(e) = trim_silence_in_place(&file_path, sensitivity) {
+                        log::warn!("Failed to trim silence from recording: {}", e);
+                    }
+                }
                 return Ok(file_path.to_string_lossy().to_string());
             } else {
                 return Err("Recording file is empty".to_string());
@@ -123,15 +808,18 @@ pub async fn stop_subprocess_recording(
 #[tauri::command]
 pub async fn cancel_subprocess_recording(
     recording_state: State<'_, RecordingProcess>,
+    vad_monitor: State<'_, VadMonitor>,
 ) -> Result<(), String> {
+    if let Some(stop_tx) = vad_monitor.0.lock().map_err(|e| e.to_string())?.take() {
+        let _ = stop_tx.send(());
+    }
+
     let mut state = recording_state.0.lock().map_err(|e| e.to_string())?;
 
-    if let Some((mut child, file_path)) = state.take() {
+    if let Some((mut backend, file_path)) = state.take() {
         log::info!("Cancelling recording...");
 
-        // Kill the process
-        let _ = child.kill();
-        let _ = child.wait();
+        let _ = backend.stop();
 
         // Delete the file
         let _ = std::fs::remove_file(&file_path);
@@ -166,13 +854,18 @@ pub async fn save_audio_temp_file(
     Ok(file_path.to_string_lossy().to_string())
 }
 
-/// Transcribe audio file using Mistral Voxtral API
+/// Transcribe audio file, dispatching to the configured provider (`mistral`
+/// by default, `openai`, or `local` for on-device Candle Whisper inference).
+/// Returns any per-segment timestamps the provider gave back alongside the text.
 #[tauri::command]
 pub async fn transcribe_audio(
+    local_model_state: State<'_, LocalWhisperModel>,
     audio_path: String,
     api_key: String,
     language: Option<String>,
-) -> Result<String, String> {
+    provider: Option<String>,
+    local_model_path: Option<String>,
+) -> Result<TranscriptionResult, String> {
     log::info!("transcribe_audio called with path: {}", audio_path);
     let path = PathBuf::from(&audio_path);
 
@@ -200,20 +893,31 @@ pub async fn transcribe_audio(
         }
     }
 
-    transcribe_with_mistral(path, api_key, language)
-        .await
-        .map_err(|e| {
-            let err_msg = format!("Transcription failed: {}", e);
-            log::error!("{}", err_msg);
-            err_msg
-        })
+    let provider = provider.unwrap_or_else(|| "mistral".to_string());
+
+    let result = match provider.as_str() {
+        "local" => {
+            let model_path = local_model_path
+                .ok_or_else(|| "stt_local_model_path is not set".to_string())?;
+            transcribe_with_local(path, PathBuf::from(model_path), language, &local_model_state)
+                .await
+                .map(|text| TranscriptionResult { text, segments: Vec::new() })
+        }
+        other => resolve_provider(other).transcribe(path, api_key, language).await,
+    };
+
+    result.map_err(|e| {
+        let err_msg = format!("Transcription failed: {}", e);
+        log::error!("{}", err_msg);
+        err_msg
+    })
 }
 
 async fn transcribe_with_mistral(
     audio_path: PathBuf,
     api_key: String,
     language: Option<String>,
-) -> Result<String> {
+) -> Result<TranscriptionResult> {
     log::info!("Starting Mistral transcription...");
     let client = reqwest::Client::new();
 
@@ -289,7 +993,265 @@ async fn transcribe_with_mistral(
 
     log::info!("Transcription successful: {} characters", transcription.text.len());
 
-    Ok(transcription.text)
+    Ok(TranscriptionResult {
+        segments: parse_segments(&transcription.segments),
+        text: transcription.text,
+    })
+}
+
+/// Transcribe audio using the OpenAI Whisper endpoint
+/// (`/v1/audio/transcriptions`, model `whisper-1`), requesting
+/// `verbose_json` so per-segment timestamps come back alongside the text.
+async fn transcribe_with_openai(
+    audio_path: PathBuf,
+    api_key: String,
+    language: Option<String>,
+) -> Result<TranscriptionResult> {
+    log::info!("Starting OpenAI transcription...");
+    let client = reqwest::Client::new();
+
+    let audio_data = tokio::fs::read(&audio_path)
+        .await
+        .context("Failed to read audio file")?;
+
+    let file_name = audio_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("audio.wav")
+        .to_string();
+
+    log::info!("Transcribing audio file: {} ({} bytes)", file_name, audio_data.len());
+
+    let file_part = multipart::Part::bytes(audio_data)
+        .file_name(file_name)
+        .mime_str("audio/wav")
+        .context("Failed to set MIME type")?;
+
+    let mut form = multipart::Form::new()
+        .text("model", "whisper-1")
+        .text("response_format", "verbose_json")
+        .text("timestamp_granularities[]", "segment")
+        .part("file", file_part);
+
+    if let Some(lang) = &language {
+        log::info!("Setting language: {}", lang);
+        form = form.text("language", lang.clone());
+    }
+
+    log::info!("Sending request to OpenAI API...");
+
+    let response = client
+        .post("https://api.openai.com/v1/audio/transcriptions")
+        .header("Authorization", format!("Bearer {}", api_key))
+        .multipart(form)
+        .send()
+        .await
+        .context("Failed to send transcription request")?;
+
+    let status = response.status();
+    let response_text = response.text().await.context("Failed to read response body")?;
+
+    log::info!("Raw API response (status {}): {}", status, response_text);
+
+    if !status.is_success() {
+        log::error!("OpenAI API error ({}): {}", status, response_text);
+        anyhow::bail!("OpenAI API error ({}): {}", status, response_text);
+    }
+
+    let transcription: TranscriptionResponse = serde_json::from_str(&response_text)
+        .context(format!("Failed to parse transcription response. Raw response: {}", response_text))?;
+
+    log::info!("Transcription successful: {} characters", transcription.text.len());
+
+    Ok(TranscriptionResult {
+        segments: parse_segments(&transcription.segments),
+        text: transcription.text,
+    })
+}
+
+/// Transcribe audio on-device using a local Whisper model (Candle), so this
+/// path works with no API key and no network access.
+async fn transcribe_with_local(
+    audio_path: PathBuf,
+    model_path: PathBuf,
+    language: Option<String>,
+    local_model_state: &State<'_, LocalWhisperModel>,
+) -> Result<String> {
+    log::info!("Starting local Whisper transcription ({:?})...", model_path);
+
+    // 16kHz mono PCM16, matching what `arecord` (and the cpal backend) write.
+    let samples = read_wav_mono_f32(&audio_path)?;
+
+    let mut guard = local_model_state.0.lock().map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    // Lazily load (and cache) the model the first time it's needed, or if the
+    // configured model path has changed since the last call.
+    let needs_reload = match guard.as_ref() {
+        Some(loaded) => loaded.model_path != model_path,
+        None => true,
+    };
+
+    if needs_reload {
+        log::info!("Loading local Whisper model from {:?}", model_path);
+        *guard = Some(load_whisper_model(&model_path)?);
+    }
+
+    let loaded = guard.as_mut().expect("model was just loaded above");
+
+    // Everything below is per-call scratch (mel spectrogram, encoder/decoder
+    // tensors). It must stay local to this function and be dropped when we
+    // return -- caching it alongside the model weights is what causes the
+    // documented unbounded memory growth on repeated macOS Candle inference.
+    let mel = whisper_model::audio::pcm_to_mel(&loaded.model.config, &samples, &loaded.mel_filters);
+    let mel_len = mel.len();
+    let mel_tensor = candle_core::Tensor::from_vec(
+        mel,
+        (1, loaded.model.config.num_mel_bins, mel_len / loaded.model.config.num_mel_bins),
+        &loaded.device,
+    )?;
+
+    let text = WhisperDecoder {
+        model: &mut loaded.model,
+        tokenizer: &loaded.tokenizer,
+        device: &loaded.device,
+    }
+    .run(&mel_tensor, language.as_deref())?;
+
+    // `mel_tensor` and every intermediate tensor created by `run` go out of
+    // scope here and release their buffers.
+    Ok(text)
+}
+
+/// Greedy-decodes a mel spectrogram into text: run the encoder once, then
+/// repeatedly feed the decoder its own output and take the highest-scoring
+/// next token until `<|endoftext|>` or a length cap is hit.
+struct WhisperDecoder<'a> {
+    model: &'a mut Whisper,
+    tokenizer: &'a Tokenizer,
+    device: &'a Device,
+}
+
+impl WhisperDecoder<'_> {
+    const MAX_TOKENS: usize = 448;
+
+    fn run(&mut self, mel: &candle_core::Tensor, language: Option<&str>) -> Result<String> {
+        let audio_features = self.model.encoder.forward(mel, true)?;
+
+        let sot = self.token_id(whisper_model::SOT_TOKEN)?;
+        let eot = self.token_id(whisper_model::EOT_TOKEN)?;
+        let transcribe = self.token_id(whisper_model::TRANSCRIBE_TOKEN)?;
+        let no_timestamps = self.token_id(whisper_model::NO_TIMESTAMPS_TOKEN)?;
+
+        let mut tokens = vec![sot];
+        if let Some(lang) = language {
+            if let Some(lang_token) = self.tokenizer.token_to_id(&format!("<|{}|>", lang)) {
+                tokens.push(lang_token);
+            }
+        }
+        tokens.push(transcribe);
+        tokens.push(no_timestamps);
+
+        for _ in 0..Self::MAX_TOKENS {
+            let tokens_tensor = candle_core::Tensor::new(tokens.as_slice(), self.device)?.unsqueeze(0)?;
+            let is_first_step = tokens.len() <= 4;
+            let hidden = self.model.decoder.forward(&tokens_tensor, &audio_features, is_first_step)?;
+            let (_, seq_len, _) = hidden.dims3()?;
+            let last_hidden = hidden.narrow(1, seq_len - 1, 1)?;
+            let logits = self.model.decoder.final_linear(&last_hidden)?.squeeze(0)?.squeeze(0)?;
+
+            let next_token = logits
+                .to_vec1::<f32>()?
+                .into_iter()
+                .enumerate()
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(idx, _)| idx as u32)
+                .context("decoder produced empty logits")?;
+
+            if next_token == eot {
+                break;
+            }
+            tokens.push(next_token);
+        }
+
+        self.tokenizer
+            .decode(&tokens, true)
+            .map_err(|e| anyhow::anyhow!("Failed to decode tokens: {}", e))
+    }
+
+    fn token_id(&self, token: &str) -> Result<u32> {
+        self.tokenizer
+            .token_to_id(token)
+            .with_context(|| format!("tokenizer is missing special token {}", token))
+    }
+}
+
+fn load_whisper_model(model_path: &std::path::Path) -> Result<LoadedWhisperModel> {
+    let device = Device::Cpu;
+
+    let tokenizer_path = model_path.with_file_name("tokenizer.json");
+    let tokenizer = Tokenizer::from_file(&tokenizer_path)
+        .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {}", e))?;
+
+    let config_path = model_path.with_file_name("config.json");
+    let config: whisper_model::Config = serde_json::from_slice(
+        &std::fs::read(&config_path).context("Failed to read Whisper config")?,
+    )
+    .context("Failed to parse Whisper config")?;
+
+    let vb = unsafe {
+        candle_nn::VarBuilder::from_mmaped_safetensors(&[model_path.to_path_buf()], candle_core::DType::F32, &device)?
+    };
+    let model = Whisper::load(vb, config)?;
+
+    let mel_filters_path = model_path.with_file_name("mel_filters.safetensors");
+    let mel_filters_tensors = candle_core::safetensors::load(&mel_filters_path, &device)
+        .context("Failed to load mel_filters.safetensors")?;
+    let mel_filters = mel_filters_tensors
+        .get("mel_filters")
+        .context("mel_filters.safetensors has no `mel_filters` tensor")?
+        .flatten_all()?
+        .to_vec1::<f32>()
+        .context("mel_filters tensor is not f32")?;
+
+    Ok(LoadedWhisperModel {
+        model_path: model_path.to_path_buf(),
+        model,
+        tokenizer,
+        mel_filters,
+        device,
+    })
+}
+
+/// Minimal WAV reader for the 16kHz mono PCM16 format written by `arecord`
+/// (and the cpal capture backend). Returns samples normalized to
+/// `f32` in `[-1.0, 1.0]`, as expected by Candle's Whisper feature extractor.
+fn read_wav_mono_f32(path: &std::path::Path) -> Result<Vec<f32>> {
+    let bytes = std::fs::read(path).context("Failed to read WAV file")?;
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        anyhow::bail!("Not a valid WAV file");
+    }
+
+    // Walk the chunk list to find "data" rather than assuming a fixed 44-byte header.
+    let mut offset = 12;
+    let mut data_range = None;
+    while offset + 8 <= bytes.len() {
+        let chunk_id = &bytes[offset..offset + 4];
+        let chunk_size =
+            u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap()) as usize;
+        let chunk_start = offset + 8;
+        if chunk_id == b"data" {
+            data_range = Some(chunk_start..(chunk_start + chunk_size).min(bytes.len()));
+            break;
+        }
+        offset = chunk_start + chunk_size;
+    }
+
+    let data_range = data_range.context("WAV file has no data chunk")?;
+
+    Ok(bytes[data_range]
+        .chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+        .collect())
 }
 
 /// Get STT settings from database
@@ -329,11 +1291,61 @@ pub async fn get_stt_settings(db: State<'_, AgentDb>) -> Result<SttSettings, Str
         )
         .ok();
 
+    let local_model_path = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'stt_local_model_path'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok();
+
+    let vad_enabled = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'stt_vad_enabled'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .map(|v| v == "true")
+        .unwrap_or(false);
+
+    let silence_ms = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'stt_silence_ms'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_else(default_silence_ms);
+
+    let sensitivity = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'stt_sensitivity'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .and_then(|v| v.parse::<f32>().ok())
+        .unwrap_or_else(default_vad_sensitivity);
+
+    let input_device = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = 'stt_input_device'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok();
+
     Ok(SttSettings {
         provider,
         api_key,
         model,
         language,
+        local_model_path,
+        vad_enabled,
+        silence_ms,
+        sensitivity,
+        input_device,
     })
 }
 
@@ -373,7 +1385,104 @@ pub async fn save_stt_settings(
         .map_err(|e| e.to_string())?;
     }
 
+    if let Some(local_model_path) = &settings.local_model_path {
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('stt_local_model_path', ?1)",
+            [local_model_path],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('stt_vad_enabled', ?1)",
+        [settings.vad_enabled.to_string()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('stt_silence_ms', ?1)",
+        [settings.silence_ms.to_string()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('stt_sensitivity', ?1)",
+        [settings.sensitivity.to_string()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    if let Some(input_device) = &settings.input_device {
+        conn.execute(
+            "INSERT OR REPLACE INTO app_settings (key, value) VALUES ('stt_input_device', ?1)",
+            [input_device],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
     log::info!("STT settings saved successfully");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 440Hz tone at 16kHz, loud enough to clear any sensitivity used below.
+    fn tone(seconds: f32, amplitude: f32) -> Vec<f32> {
+        let n = (VAD_SAMPLE_RATE as f32 * seconds) as usize;
+        (0..n)
+            .map(|i| {
+                amplitude
+                    * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / VAD_SAMPLE_RATE as f32).sin()
+            })
+            .collect()
+    }
+
+    fn silence(seconds: f32) -> Vec<f32> {
+        vec![0.0; (VAD_SAMPLE_RATE as f32 * seconds) as usize]
+    }
+
+    #[test]
+    fn vad_detects_tone_over_silence() {
+        let mut vad = VoiceActivityDetector::new(2.0);
+        // Prime the noise floor on a run of silent frames.
+        for frame in silence(0.5).chunks(VAD_FRAME_SAMPLES) {
+            if frame.len() == VAD_FRAME_SAMPLES {
+                assert!(!vad.is_speech(frame));
+            }
+        }
+        let loud_frame = tone(0.02, 0.9);
+        assert!(vad.is_speech(&loud_frame[..VAD_FRAME_SAMPLES]));
+    }
+
+    #[test]
+    fn trim_silence_strips_leading_and_trailing_quiet() {
+        let samples: Vec<f32> = silence(0.5)
+            .into_iter()
+            .chain(tone(0.5, 0.9))
+            .chain(silence(0.5))
+            .collect();
+
+        let trimmed = trim_silence(&samples, 2.0);
+        assert!(trimmed.len() < samples.len());
+        assert!(!trimmed.is_empty());
+    }
+
+    #[test]
+    fn wav_round_trip_preserves_samples() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("stt_wav_round_trip_{:?}.wav", std::thread::current().id()));
+
+        let original = vec![0.0_f32, 0.5, -0.5, 1.0, -1.0, 0.25];
+        write_wav_mono_i16(&path, &original).expect("write wav");
+        let read_back = read_wav_mono_f32(&path).expect("read wav");
+
+        assert_eq!(read_back.len(), original.len());
+        for (a, b) in original.iter().zip(read_back.iter()) {
+            assert!((a - b).abs() < 1e-3, "expected {a}, got {b}");
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+}